@@ -140,6 +140,14 @@ func main() {
 		}
 		useVMAC = uv
 	}
+	vrrpPeerFilter := config.DefaultEngineConfig().VRRPPeerFilter
+	if cfg.HasOption("cluster", "vrrp_peer_filter") {
+		pf, err := cfg.GetBool("cluster", "vrrp_peer_filter")
+		if err != nil {
+			log.Exitf("Unable to get vrrp_peer_filter: %v", err)
+		}
+		vrrpPeerFilter = pf
+	}
 	garpInterval := config.DefaultEngineConfig().GratuitousARPInterval
 	if cfg.HasOption("cluster", "garp_interval_sec") {
 		it, err := cfg.GetInt("cluster", "garp_interval_sec")
@@ -254,6 +262,7 @@ func main() {
 	engineCfg.SocketPath = *socketPath
 	engineCfg.VRID = vrid
 	engineCfg.UseVMAC = useVMAC
+	engineCfg.VRRPPeerFilter = vrrpPeerFilter
 	engineCfg.GratuitousARPInterval = garpInterval
 
 	// removes previous leftover socket.
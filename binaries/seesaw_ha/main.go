@@ -117,7 +117,7 @@ func main() {
 	engine := engine()
 	config := config(engine)
 	log.Infof("Received HAConfig: %v", config)
-	conn, err := ha.NewIPHAConn(config.LocalAddr, config.RemoteAddr)
+	conn, err := ha.NewIPHAConn(config.LocalAddr, config.RemoteAddr, config.AllowedPeers...)
 	if err != nil {
 		log.Fatalf("%v", err)
 	}
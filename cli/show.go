@@ -158,6 +158,7 @@ func showHAStatus(cli *SeesawCLI, args []string) error {
 	printVal("Transitions:", ha.Transitions)
 	printVal("Advertisements Sent:", ha.Sent)
 	printVal("Advertisements Rcvd:", ha.Received)
+	printVal("Adverts Rejected:", ha.RejectedPeer)
 	printVal("Last Update:", ha.LastUpdate.Format(timeStamp))
 
 	return nil
@@ -106,6 +106,7 @@ type HAStatus struct {
 	Sent           uint64
 	Received       uint64
 	ReceivedQueued uint64
+	RejectedPeer   uint64
 	Transitions    uint64
 }
 
@@ -423,11 +424,12 @@ type ClusterStatus struct {
 // HAConfig represents the high availability configuration for a node in a
 // Seesaw cluster.
 type HAConfig struct {
-	Enabled    bool
-	LocalAddr  net.IP
-	RemoteAddr net.IP
-	Priority   uint8
-	VRID       uint8
+	Enabled      bool
+	LocalAddr    net.IP
+	RemoteAddr   net.IP
+	AllowedPeers []net.IP // If non-empty, only accept advertisements from these addresses.
+	Priority     uint8
+	VRID         uint8
 }
 
 // VLAN represents a VLAN interface configuration.
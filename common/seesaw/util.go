@@ -154,6 +154,13 @@ func (h *HAConfig) Copy(c *HAConfig) {
 	h.Enabled = c.Enabled
 	h.LocalAddr = copyIP(c.LocalAddr)
 	h.RemoteAddr = copyIP(c.RemoteAddr)
+	h.AllowedPeers = nil
+	if c.AllowedPeers != nil {
+		h.AllowedPeers = make([]net.IP, len(c.AllowedPeers))
+		for i, ip := range c.AllowedPeers {
+			h.AllowedPeers[i] = copyIP(ip)
+		}
+	}
 	h.Priority = c.Priority
 	h.VRID = c.VRID
 }
@@ -170,14 +177,15 @@ func (h *HAConfig) Equal(other *HAConfig) bool {
 	return h.Enabled == other.Enabled &&
 		h.LocalAddr.Equal(other.LocalAddr) &&
 		h.RemoteAddr.Equal(other.RemoteAddr) &&
+		equalIPs(h.AllowedPeers, other.AllowedPeers) &&
 		h.Priority == other.Priority &&
 		h.VRID == other.VRID
 }
 
 // String returns the string representation of an HAConfig.
 func (h HAConfig) String() string {
-	return fmt.Sprintf("Enabled: %v, LocalAddr: %v, RemoteAddr: %v, Priority: %d, VRID: %d",
-		h.Enabled, h.LocalAddr, h.RemoteAddr, h.Priority, h.VRID)
+	return fmt.Sprintf("Enabled: %v, LocalAddr: %v, RemoteAddr: %v, AllowedPeers: %v, Priority: %d, VRID: %d",
+		h.Enabled, h.LocalAddr, h.RemoteAddr, h.AllowedPeers, h.Priority, h.VRID)
 }
 
 // Equal reports whether this VLAN is equal to the given VLAN.
@@ -255,6 +263,19 @@ func copyIP(src net.IP) net.IP {
 	return net.IP(copyBytes(src))
 }
 
+// equalIPs reports whether two lists of IP addresses are equal.
+func equalIPs(a, b []net.IP) bool {
+	if len(a) != len(b) {
+		return false
+	}
+	for i := range a {
+		if !a[i].Equal(b[i]) {
+			return false
+		}
+	}
+	return true
+}
+
 // copyIPMask creates a copy of an IPMask.
 func copyIPMask(src net.IPMask) net.IPMask {
 	return net.IPMask(copyBytes(src))
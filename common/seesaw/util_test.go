@@ -147,6 +147,14 @@ var haConfigs = []HAConfig{
 		Priority:   200,
 		VRID:       99,
 	},
+	{
+		Enabled:      true,
+		LocalAddr:    net.ParseIP("1.2.3.4"),
+		RemoteAddr:   net.ParseIP("224.0.0.18"),
+		AllowedPeers: []net.IP{net.ParseIP("1.2.3.5"), net.ParseIP("2012::5")},
+		Priority:     100,
+		VRID:         60,
+	},
 }
 
 func TestHAConfigClone(t *testing.T) {
@@ -96,4 +96,5 @@ type EngineConfig struct {
 	VMAC                    string        // The VMAC address to use for the load balancing network interface.
 	VRID                    uint8         // The VRRP virtual router ID for the cluster.
 	VRRPDestIP              net.IP        // The destination IP for VRRP advertisements.
+	VRRPPeerFilter          bool          // Only accept VRRP advertisements from the peer's addresses.
 }
@@ -219,12 +219,21 @@ func (e *Engine) haConfig() (*seesaw.HAConfig, error) {
 	if localAddr == nil {
 		localAddr = e.config.Node.IPv6Addr
 	}
+	var allowedPeers []net.IP
+	if e.config.VRRPPeerFilter {
+		for _, ip := range []net.IP{e.config.Peer.IPv4Addr, e.config.Peer.IPv6Addr} {
+			if ip != nil {
+				allowedPeers = append(allowedPeers, ip)
+			}
+		}
+	}
 	return &seesaw.HAConfig{
-		Enabled:    n.State != spb.HaState_DISABLED,
-		LocalAddr:  localAddr,
-		RemoteAddr: e.config.VRRPDestIP,
-		Priority:   n.Priority,
-		VRID:       e.config.VRID,
+		Enabled:      n.State != spb.HaState_DISABLED,
+		LocalAddr:    localAddr,
+		RemoteAddr:   e.config.VRRPDestIP,
+		AllowedPeers: allowedPeers,
+		Priority:     n.Priority,
+		VRID:         e.config.VRID,
 	}, nil
 }
 
@@ -143,6 +143,7 @@ func (h *haManager) setStatus(s seesaw.HAStatus) {
 	h.status.Since = s.Since
 	h.status.Sent = s.Sent
 	h.status.Received = s.Received
+	h.status.RejectedPeer = s.RejectedPeer
 	h.status.Transitions = s.Transitions
 	h.statusLock.Unlock()
 }
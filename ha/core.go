@@ -35,6 +35,7 @@ import (
 type HAConn interface {
 	send(advert *advertisement, timeout time.Duration) error
 	receive() (*advertisement, error)
+	rejectedPeerCount() uint64
 }
 
 // advertisement represents a VRRPv3 advertisement packet.  Field names and sizes are per RFC 5798.
@@ -146,6 +147,7 @@ func (n *Node) status() seesaw.HAStatus {
 	n.haStatus.Sent = atomic.LoadUint64(&n.sendCount)
 	n.haStatus.Received = atomic.LoadUint64(&n.receiveCount)
 	n.haStatus.ReceivedQueued = uint64(len(n.recvChannel))
+	n.haStatus.RejectedPeer = n.conn.rejectedPeerCount()
 	return n.haStatus
 }
 
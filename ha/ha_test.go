@@ -19,6 +19,9 @@ package ha
 // This file contains the unit tests for the ha package.
 
 import (
+	"net"
+	"reflect"
+	"syscall"
 	"testing"
 	"time"
 
@@ -42,6 +45,10 @@ func (h *dummyHAConn) send(advert *advertisement, timeout time.Duration) error {
 	return nil
 }
 
+func (h *dummyHAConn) rejectedPeerCount() uint64 {
+	return 0
+}
+
 func newTestNode() *Node {
 	haConn := &dummyHAConn{}
 	engine := &DummyEngine{}
@@ -202,3 +209,203 @@ func TestIPChecksum(t *testing.T) {
 		t.Errorf("Want checksum %x but was %x", want, chksum)
 	}
 }
+
+func TestIPConnSockopts(t *testing.T) {
+	const ifIndex = 3
+	ipv6Recv := []sockopt{
+		{syscall.IPPROTO_IPV6, syscall.IPV6_RECVHOPLIMIT, 1},
+		{syscall.IPPROTO_IPV6, syscall.IPV6_RECVPKTINFO, 1},
+	}
+	tests := []struct {
+		desc  string
+		laddr string
+		raddr string
+		want  []sockopt
+	}{
+		{
+			"IPv4 unicast",
+			"192.168.1.1", "192.168.1.2",
+			[]sockopt{
+				{syscall.IPPROTO_IP, syscall.IP_TTL, 255},
+			},
+		},
+		{
+			"IPv4 multicast",
+			"192.168.1.1", "224.0.0.18",
+			[]sockopt{
+				{syscall.IPPROTO_IP, syscall.IP_MULTICAST_TTL, 255},
+				{syscall.IPPROTO_IP, syscall.IP_MULTICAST_LOOP, 0},
+			},
+		},
+		{
+			"IPv6 unicast",
+			"2001:db8::1", "2001:db8::2",
+			append([]sockopt{
+				{syscall.IPPROTO_IPV6, syscall.IPV6_UNICAST_HOPS, 255},
+			}, ipv6Recv...),
+		},
+		{
+			"IPv6 multicast",
+			"fe80::1", "ff02::12",
+			append([]sockopt{
+				{syscall.IPPROTO_IPV6, syscall.IPV6_MULTICAST_HOPS, 255},
+				{syscall.IPPROTO_IPV6, syscall.IPV6_MULTICAST_LOOP, 0},
+				{syscall.IPPROTO_IPV6, syscall.IPV6_MULTICAST_IF, ifIndex},
+			}, ipv6Recv...),
+		},
+	}
+	for _, test := range tests {
+		got := ipConnSockopts(net.ParseIP(test.laddr), net.ParseIP(test.raddr), ifIndex)
+		if !reflect.DeepEqual(got, test.want) {
+			t.Errorf("ipConnSockopts() for %s = %v, want %v", test.desc, got, test.want)
+		}
+	}
+}
+
+func TestAdvertisementRoundTrip(t *testing.T) {
+	tests := []struct {
+		desc     string
+		sender   string
+		receiver string
+		group    string
+	}{
+		{"IPv4 unicast", "192.168.1.1", "192.168.1.2", ""},
+		{"IPv4 multicast", "192.168.1.1", "192.168.1.2", "224.0.0.18"},
+		{"IPv6 unicast", "2001:db8::1", "2001:db8::2", ""},
+		{"IPv6 multicast", "fe80::1", "fe80::2", "ff02::12"},
+	}
+	for _, test := range tests {
+		sender := &IPHAConn{laddr: net.ParseIP(test.sender), raddr: net.ParseIP(test.receiver)}
+		receiver := &IPHAConn{laddr: net.ParseIP(test.receiver), raddr: net.ParseIP(test.sender)}
+		if test.group != "" {
+			sender.raddr = net.ParseIP(test.group)
+			receiver.raddr = net.ParseIP(test.group)
+		}
+
+		want := vrrpTestAdvert
+		b, err := sender.marshalAdvertisement(&want)
+		if err != nil {
+			t.Errorf("%s: marshalAdvertisement() failed: %v", test.desc, err)
+			continue
+		}
+		p := &packet{src: sender.laddr, dst: sender.raddr, ttl: 255, payload: b}
+		got, err := receiver.parsePacket(p)
+		if err != nil || got == nil {
+			t.Errorf("%s: parsePacket() = %v, %v, want %v", test.desc, got, err, want)
+		} else if *got != want {
+			t.Errorf("%s: parsePacket() = %v, want %v", test.desc, *got, want)
+		}
+
+		// Packets that have been forwarded or corrupted must be dropped.
+		p.ttl = 254
+		if got, err := receiver.parsePacket(p); got != nil || err != nil {
+			t.Errorf("%s: parsePacket() with TTL %d = %v, %v, want nil, nil", test.desc, p.ttl, got, err)
+		}
+		p.ttl = 255
+		p.payload[len(p.payload)-1] ^= 0xff
+		if got, err := receiver.parsePacket(p); got != nil || err != nil {
+			t.Errorf("%s: parsePacket() with bad checksum = %v, %v, want nil, nil", test.desc, got, err)
+		}
+	}
+}
+
+func TestAcceptSource(t *testing.T) {
+	tests := []struct {
+		desc    string
+		allowed []string
+		src     string
+		want    bool
+	}{
+		{"No allowed peers", nil, "192.168.1.3", true},
+		{"IPv4 allowed peer", []string{"192.168.1.2"}, "192.168.1.2", true},
+		{"IPv4 other source", []string{"192.168.1.2"}, "192.168.1.3", false},
+		{"IPv6 allowed peer", []string{"192.168.1.2", "2001:db8::2"}, "2001:db8::2", true},
+		{"IPv6 other source", []string{"192.168.1.2", "2001:db8::2"}, "2001:db8::3", false},
+	}
+	for _, test := range tests {
+		c := &IPHAConn{raddr: net.ParseIP("224.0.0.18")}
+		for _, peer := range test.allowed {
+			c.allowedPeers = append(c.allowedPeers, net.ParseIP(peer))
+		}
+		if got := c.acceptSource(net.ParseIP(test.src)); got != test.want {
+			t.Errorf("acceptSource() for %s = %t, want %t", test.desc, got, test.want)
+		}
+	}
+}
+
+func TestRejectedPeer(t *testing.T) {
+	group := net.ParseIP("224.0.0.18")
+	sender := &IPHAConn{laddr: net.ParseIP("192.168.1.3"), raddr: group}
+	receiver := &IPHAConn{
+		laddr:        net.ParseIP("192.168.1.1"),
+		raddr:        group,
+		allowedPeers: []net.IP{net.ParseIP("192.168.1.2")},
+	}
+
+	advert := vrrpTestAdvert
+	b, err := sender.marshalAdvertisement(&advert)
+	if err != nil {
+		t.Fatalf("marshalAdvertisement() failed: %v", err)
+	}
+	p := &packet{src: sender.laddr, dst: group, ttl: 255, payload: b}
+	for i := 1; i <= 3; i++ {
+		if got, err := receiver.parsePacket(p); got != nil || err != nil {
+			t.Errorf("parsePacket() from %v = %v, %v, want nil, nil", p.src, got, err)
+		}
+		if got := receiver.rejectedPeerCount(); got != uint64(i) {
+			t.Errorf("rejectedPeerCount() = %d, want %d", got, i)
+		}
+	}
+}
+
+func TestLogUnexpectedPeer(t *testing.T) {
+	c := &IPHAConn{raddr: net.ParseIP("224.0.0.18")}
+	src1 := net.ParseIP("192.168.1.3")
+	src2 := net.ParseIP("192.168.1.4")
+	now := time.Now()
+
+	tests := []struct {
+		desc string
+		src  net.IP
+		at   time.Duration
+		want bool
+	}{
+		{"first advertisement", src1, 0, true},
+		{"repeat within interval", src1, unexpectedPeerLogInterval / 2, false},
+		{"other source", src2, unexpectedPeerLogInterval / 2, true},
+		{"repeat after interval", src1, unexpectedPeerLogInterval, true},
+		{"other source repeat within interval", src2, unexpectedPeerLogInterval, false},
+	}
+	for _, test := range tests {
+		if got := c.logUnexpectedPeer(test.src, now.Add(test.at)); got != test.want {
+			t.Errorf("logUnexpectedPeer() for %s = %t, want %t", test.desc, got, test.want)
+		}
+	}
+}
+
+func TestLogUnexpectedPeerLimit(t *testing.T) {
+	c := &IPHAConn{raddr: net.ParseIP("224.0.0.18")}
+	now := time.Now()
+	for i := 0; i < maxUnexpectedPeers; i++ {
+		src := net.IPv4(10, 0, 0, byte(i+1))
+		if !c.logUnexpectedPeer(src, now) {
+			t.Errorf("logUnexpectedPeer() for source %d of %d = false, want true", i+1, maxUnexpectedPeers)
+		}
+	}
+
+	extra := net.IPv4(10, 0, 1, 1)
+	if c.logUnexpectedPeer(extra, now) {
+		t.Errorf("logUnexpectedPeer() beyond %d sources = true, want false", maxUnexpectedPeers)
+	}
+	if got := len(c.unexpectedPeers); got != maxUnexpectedPeers {
+		t.Errorf("Tracking %d unexpected sources, want %d", got, maxUnexpectedPeers)
+	}
+
+	// Once the tracked sources expire, new sources are logged again.
+	if !c.logUnexpectedPeer(extra, now.Add(unexpectedPeerLogInterval)) {
+		t.Errorf("logUnexpectedPeer() after interval = false, want true")
+	}
+	if got := len(c.unexpectedPeers); got != 1 {
+		t.Errorf("Tracking %d unexpected sources after expiry, want 1", got)
+	}
+}
@@ -31,6 +31,7 @@ import (
 	"fmt"
 	"net"
 	"os"
+	"sync/atomic"
 	"syscall"
 	"time"
 
@@ -56,6 +57,20 @@ type ipv6PseudoHeader struct {
 // IPConn creates a net.IPConn using the given local and remote addresses using IP protocol
 // 112 (VRRP).
 func IPConn(localAddr, remoteAddr net.IP) (*net.IPConn, error) {
+	var ifIndex int
+	if localAddr.To4() == nil && remoteAddr.IsMulticast() {
+		iface, err := findInterface(localAddr)
+		if err != nil {
+			return nil, err
+		}
+		ifIndex = iface.Index
+	}
+	return ipConn(localAddr, remoteAddr, ifIndex)
+}
+
+// ipConn is like IPConn, but takes the index of the interface with the local address
+// rather than looking it up. ifIndex is only used for IPv6 multicast.
+func ipConn(localAddr, remoteAddr net.IP, ifIndex int) (*net.IPConn, error) {
 	c, err := net.ListenIP("ip:112", &net.IPAddr{IP: localAddr})
 	if err != nil {
 		return nil, err
@@ -67,61 +82,63 @@ func IPConn(localAddr, remoteAddr net.IP) (*net.IPConn, error) {
 	}
 	defer f.Close()
 
+	for _, so := range ipConnSockopts(localAddr, remoteAddr, ifIndex) {
+		if err := setsockopt(f, so.level, so.opt, so.value); err != nil {
+			return nil, err
+		}
+	}
+
+	log.Infof("ha.IPConn: laddr=%v, raddr=%v", localAddr, remoteAddr)
+	return c, nil
+}
+
+// sockopt is an integer socket option.
+type sockopt struct {
+	level, opt, value int
+}
+
+// ipConnSockopts returns the socket options that IPConn sets for the given local and
+// remote addresses.
+func ipConnSockopts(localAddr, remoteAddr net.IP, ifIndex int) []sockopt {
+	var opts []sockopt
 	ip4 := localAddr.To4()
 	switch {
 	case ip4 != nil && !remoteAddr.IsMulticast():
 		// IPv4 unicast
 		// TTL = 255 per VRRP spec
-		if err := setsockopt(f, syscall.IPPROTO_IP, syscall.IP_TTL, 255); err != nil {
-			return nil, err
-		}
+		opts = append(opts, sockopt{syscall.IPPROTO_IP, syscall.IP_TTL, 255})
 
 	case ip4 != nil && remoteAddr.IsMulticast():
 		// IPv4 multicast
 		// TTL = 255 per VRRP spec
-		if err := setsockopt(f, syscall.IPPROTO_IP, syscall.IP_MULTICAST_TTL, 255); err != nil {
-			return nil, err
-		}
+		opts = append(opts, sockopt{syscall.IPPROTO_IP, syscall.IP_MULTICAST_TTL, 255})
 		// We don't want to receive our own messages.
-		if err := setsockopt(f, syscall.IPPROTO_IP, syscall.IP_MULTICAST_LOOP, 0); err != nil {
-			return nil, err
-		}
+		opts = append(opts, sockopt{syscall.IPPROTO_IP, syscall.IP_MULTICAST_LOOP, 0})
 
 	case ip4 == nil && !remoteAddr.IsMulticast():
 		// IPv6 unicast
 		// HOPLIMIT = 255 per VRRP spec
-		if err := setsockopt(f, syscall.IPPROTO_IPV6, syscall.IPV6_UNICAST_HOPS, 255); err != nil {
-			return nil, err
-		}
+		opts = append(opts, sockopt{syscall.IPPROTO_IPV6, syscall.IPV6_UNICAST_HOPS, 255})
 
 	case ip4 == nil && remoteAddr.IsMulticast():
 		// IPv6 multicast
 		// HOPLIMIT = 255 per VRRP spec
-		if err := setsockopt(f, syscall.IPPROTO_IPV6, syscall.IPV6_MULTICAST_HOPS, 255); err != nil {
-			return nil, err
-		}
+		opts = append(opts, sockopt{syscall.IPPROTO_IPV6, syscall.IPV6_MULTICAST_HOPS, 255})
 		// We don't want to receive our own messages.
-		if err := setsockopt(f, syscall.IPPROTO_IPV6, syscall.IPV6_MULTICAST_LOOP, 0); err != nil {
-			return nil, err
-		}
-
+		opts = append(opts, sockopt{syscall.IPPROTO_IPV6, syscall.IPV6_MULTICAST_LOOP, 0})
+		// Send via the interface with the local address, rather than leaving
+		// the choice of interface for the link-local group to the kernel.
+		opts = append(opts, sockopt{syscall.IPPROTO_IPV6, syscall.IPV6_MULTICAST_IF, ifIndex})
 	}
 
 	if ip4 == nil {
 		// IPv6 unicast and multicast
 		// Request that the ancillary data for received packets include the hop limit and the
 		// destination address.
-
-		if err := setsockopt(f, syscall.IPPROTO_IPV6, syscall.IPV6_RECVHOPLIMIT, 1); err != nil {
-			return nil, err
-		}
-		if err := setsockopt(f, syscall.IPPROTO_IPV6, syscall.IPV6_RECVPKTINFO, 1); err != nil {
-			return nil, err
-		}
+		opts = append(opts, sockopt{syscall.IPPROTO_IPV6, syscall.IPV6_RECVHOPLIMIT, 1})
+		opts = append(opts, sockopt{syscall.IPPROTO_IPV6, syscall.IPV6_RECVPKTINFO, 1})
 	}
-
-	log.Infof("ha.IPConn: laddr=%v, raddr=%v", localAddr, remoteAddr)
-	return c, nil
+	return opts
 }
 
 func setsockopt(f *os.File, level, opt, value int) error {
@@ -131,17 +148,45 @@ func setsockopt(f *os.File, level, opt, value int) error {
 	return nil
 }
 
+const (
+	// unexpectedPeerLogInterval is the minimum interval between warnings about
+	// advertisements from the same unexpected source.
+	unexpectedPeerLogInterval = time.Minute
+
+	// maxUnexpectedPeers is the maximum number of unexpected sources that are tracked
+	// for rate limiting. Sources are not authenticated, so this bounds the memory and
+	// time spent on spoofed advertisements.
+	maxUnexpectedPeers = 16
+)
+
 // IPHAConn implements the HAConn interface.
 type IPHAConn struct {
-	sendConn *net.IPConn
-	recvConn *net.IPConn
-	laddr    net.IP
-	raddr    net.IP
+	sendConn      *net.IPConn
+	recvConn      *net.IPConn
+	laddr         net.IP
+	raddr         net.IP
+	allowedPeers  []net.IP
+	rejectedPeers uint64
+
+	// unexpectedPeers tracks when a warning was last logged for each unexpected source.
+	unexpectedPeers map[string]time.Time
 }
 
-// NewIPHAConn creates a new IPHAConn.
-func NewIPHAConn(laddr, raddr net.IP) (HAConn, error) {
-	sendConn, err := IPConn(laddr, raddr)
+// NewIPHAConn creates a new IPHAConn. If allowedPeers is non-empty, advertisements from
+// any other source address are dropped.
+func NewIPHAConn(laddr, raddr net.IP, allowedPeers ...net.IP) (HAConn, error) {
+	// For IPv6 multicast, the interface with the local address is used both to send
+	// advertisements and to join the group.
+	var ifIndex int
+	if raddr.IsMulticast() && raddr.To4() == nil {
+		iface, err := findInterface(laddr)
+		if err != nil {
+			return nil, err
+		}
+		ifIndex = iface.Index
+	}
+
+	sendConn, err := ipConn(laddr, raddr, ifIndex)
 	if err != nil {
 		return nil, err
 	}
@@ -157,17 +202,18 @@ func NewIPHAConn(laddr, raddr net.IP) (HAConn, error) {
 			}
 		} else {
 			log.Infof("Using IPv6 multicast")
-			if err = JoinMulticastIPv6(recvConn, raddr, laddr); err != nil {
+			if err = joinMulticastIPv6(recvConn, raddr, ifIndex); err != nil {
 				return nil, err
 			}
 		}
 	}
 
 	return &IPHAConn{
-		sendConn: sendConn,
-		recvConn: recvConn,
-		laddr:    laddr,
-		raddr:    raddr,
+		sendConn:     sendConn,
+		recvConn:     recvConn,
+		laddr:        laddr,
+		raddr:        raddr,
+		allowedPeers: allowedPeers,
 	}, nil
 }
 
@@ -203,6 +249,16 @@ func ListenMulticastIPv4(gaddr, laddr net.IP) (*net.IPConn, error) {
 // JoinMulticastIPv6 joins the multicast group address given by gaddr. laddr specifies which
 // network interface to use when joining the group.
 func JoinMulticastIPv6(c *net.IPConn, gaddr, laddr net.IP) error {
+	iface, err := findInterface(laddr)
+	if err != nil {
+		return err
+	}
+	return joinMulticastIPv6(c, gaddr, iface.Index)
+}
+
+// joinMulticastIPv6 is like JoinMulticastIPv6, but takes the index of the network interface
+// to use when joining the group.
+func joinMulticastIPv6(c *net.IPConn, gaddr net.IP, ifIndex int) error {
 	f, err := c.File()
 	if err != nil {
 		return err
@@ -211,11 +267,7 @@ func JoinMulticastIPv6(c *net.IPConn, gaddr, laddr net.IP) error {
 
 	mreq := &syscall.IPv6Mreq{}
 	copy(mreq.Multiaddr[:], gaddr.To16())
-	iface, err := findInterface(laddr)
-	if err != nil {
-		return err
-	}
-	mreq.Interface = uint32(iface.Index)
+	mreq.Interface = uint32(ifIndex)
 
 	err = syscall.SetsockoptIPv6Mreq(int(f.Fd()), syscall.IPPROTO_IPV6, syscall.IPV6_JOIN_GROUP, mreq)
 	if err != nil {
@@ -270,7 +322,14 @@ func (c *IPHAConn) receive() (*advertisement, error) {
 			}
 		}
 		return nil, err
-	} else if len(p.payload) != vrrpAdvertSize {
+	}
+	return c.parsePacket(p)
+}
+
+// parsePacket translates a received IP packet into an advertisement. If the packet is to be
+// ignored, parsePacket returns (nil, nil).
+func (c *IPHAConn) parsePacket(p *packet) (*advertisement, error) {
+	if len(p.payload) != vrrpAdvertSize {
 		// Ignore
 		return nil, nil
 	}
@@ -287,6 +346,13 @@ func (c *IPHAConn) receive() (*advertisement, error) {
 		return nil, nil
 	}
 
+	// Drop packets from sources that are not allowed to peer with us.
+	if !c.acceptSource(p.src) {
+		atomic.AddUint64(&c.rejectedPeers, 1)
+		c.logUnexpectedPeer(p.src, time.Now())
+		return nil, nil
+	}
+
 	// Drop packets that don't have a TTL/HOPLIMIT.
 	if p.ttl != 255 {
 		log.Warningf("IPHAConn.receive: Invalid TTL/HOPLIMIT %d from %v", p.ttl, p.src)
@@ -308,6 +374,53 @@ func (c *IPHAConn) receive() (*advertisement, error) {
 	return advert, nil
 }
 
+// acceptSource returns true if advertisements from src should be processed. If no allowed
+// peers are configured, advertisements from any source are accepted.
+func (c *IPHAConn) acceptSource(src net.IP) bool {
+	if len(c.allowedPeers) == 0 {
+		return true
+	}
+	for _, peer := range c.allowedPeers {
+		if src.Equal(peer) {
+			return true
+		}
+	}
+	return false
+}
+
+// rejectedPeerCount returns the number of advertisements that have been dropped because
+// they were not from an allowed peer.
+func (c *IPHAConn) rejectedPeerCount() uint64 {
+	return atomic.LoadUint64(&c.rejectedPeers)
+}
+
+// logUnexpectedPeer logs a warning about an advertisement from an unexpected source, at
+// most once per unexpectedPeerLogInterval for each source. At most maxUnexpectedPeers
+// sources are tracked; warnings for further sources are suppressed until tracked sources
+// expire. It returns true if a warning was logged.
+func (c *IPHAConn) logUnexpectedPeer(src net.IP, now time.Time) bool {
+	key := src.String()
+	if last, ok := c.unexpectedPeers[key]; ok && now.Sub(last) < unexpectedPeerLogInterval {
+		return false
+	}
+	if c.unexpectedPeers == nil {
+		c.unexpectedPeers = make(map[string]time.Time)
+	}
+	if len(c.unexpectedPeers) >= maxUnexpectedPeers {
+		for peer, last := range c.unexpectedPeers {
+			if now.Sub(last) >= unexpectedPeerLogInterval {
+				delete(c.unexpectedPeers, peer)
+			}
+		}
+		if len(c.unexpectedPeers) >= maxUnexpectedPeers {
+			return false
+		}
+	}
+	c.unexpectedPeers[key] = now
+	log.Warningf("IPHAConn.receive: Dropping advertisements from unexpected peer %v (allowed %v)", src, c.allowedPeers)
+	return true
+}
+
 // packet encapsulates information about a received IP packet.
 type packet struct {
 	src     net.IP
@@ -420,24 +533,34 @@ func (c *IPHAConn) send(advert *advertisement, timeout time.Duration) error {
 		return err
 	}
 
+	b, err := c.marshalAdvertisement(advert)
+	if err != nil {
+		return err
+	}
+
+	if _, err := c.sendConn.WriteToIP(b, &net.IPAddr{IP: c.raddr}); err != nil {
+		return err
+	}
+
+	return nil
+}
+
+// marshalAdvertisement translates an advertisement into the VRRP payload to be sent to this
+// IPHAConn's remote address, computing the checksum if it is not already set.
+func (c *IPHAConn) marshalAdvertisement(advert *advertisement) ([]byte, error) {
 	if advert.Checksum == 0 {
 		chksum, err := checksum(advert, c.laddr, c.raddr)
 		if err != nil {
-			return fmt.Errorf("IPHAConn.send: checksum failed: %v", err)
+			return nil, fmt.Errorf("IPHAConn.send: checksum failed: %v", err)
 		}
 		advert.Checksum = chksum
 	}
 
 	buf := new(bytes.Buffer)
 	if err := binary.Write(buf, binary.BigEndian, advert); err != nil {
-		return err
-	}
-
-	if _, err := c.sendConn.WriteToIP(buf.Bytes(), &net.IPAddr{IP: c.raddr}); err != nil {
-		return err
+		return nil, err
 	}
-
-	return nil
+	return buf.Bytes(), nil
 }
 
 func checksum(advert *advertisement, srcIP, dstIP net.IP) (uint16, error) {
@@ -20,8 +20,10 @@ package ipvs
 import (
 	"errors"
 	"fmt"
+	"math"
 	"net"
 	"syscall"
+	"time"
 	"unsafe"
 
 	"github.com/google/seesaw/netlink"
@@ -72,6 +74,12 @@ type ipvsService struct {
 	PersistenceEngine string        `netlink:"attr:11,omitempty,optional"`
 }
 
+type ipvsConfig struct {
+	TimeoutTCP    uint32 `netlink:"attr:4,omitempty,optional"`
+	TimeoutTCPFin uint32 `netlink:"attr:5,omitempty,optional"`
+	TimeoutUDP    uint32 `netlink:"attr:6,omitempty,optional"`
+}
+
 type ipvsCommand struct {
 	Service     *ipvsService     `netlink:"attr:1,omitempty,optional"`
 	Destination *ipvsDestination `netlink:"attr:2,omitempty,optional"`
@@ -170,6 +178,53 @@ func (ipvsDst ipvsDestination) toDestination() *Destination {
 	return dst
 }
 
+// maxTimeout is the largest connection timeout that can safely be given to IPVS.
+// The kernel stores timeouts as an int and converts them to jiffies, so larger
+// values are either rejected or overflow, depending on the kernel version. This
+// bound is safe for HZ up to 1000.
+const maxTimeout = math.MaxInt32 / 1000 * time.Second
+
+// timeoutSeconds converts a connection timeout to its IPVS representation.
+// The timeout must either be zero or between one second and maxTimeout, since
+// anything else would be truncated or overflow.
+func timeoutSeconds(name string, d time.Duration) (uint32, error) {
+	if d != 0 && (d < time.Second || d > maxTimeout) {
+		return 0, fmt.Errorf("invalid %s timeout %v - must be 0 or between 1s and %v", name, d, maxTimeout)
+	}
+	return uint32(d / time.Second), nil
+}
+
+// newIPVSConfig converts timeouts to their IPVS representation.
+func newIPVSConfig(t *Timeouts) (*ipvsConfig, error) {
+	tcp, err := timeoutSeconds("TCP", t.TCP)
+	if err != nil {
+		return nil, err
+	}
+	tcpFin, err := timeoutSeconds("TCP FIN", t.TCPFin)
+	if err != nil {
+		return nil, err
+	}
+	udp, err := timeoutSeconds("UDP", t.UDP)
+	if err != nil {
+		return nil, err
+	}
+	return &ipvsConfig{
+		TimeoutTCP:    tcp,
+		TimeoutTCPFin: tcpFin,
+		TimeoutUDP:    udp,
+	}, nil
+}
+
+// toTimeouts converts a config entry from its IPVS representation to the Go
+// equivalent Timeouts structure.
+func (ipvsCfg ipvsConfig) toTimeouts() *Timeouts {
+	return &Timeouts{
+		TCP:    time.Duration(ipvsCfg.TimeoutTCP) * time.Second,
+		TCPFin: time.Duration(ipvsCfg.TimeoutTCPFin) * time.Second,
+		UDP:    time.Duration(ipvsCfg.TimeoutUDP) * time.Second,
+	}
+}
+
 // IPProto specifies the protocol encapsulated within an IP datagram.
 type IPProto uint16
 
@@ -333,6 +388,19 @@ type DestinationStats struct {
 	PersistConns  uint32
 }
 
+// Timeouts specifies the IPVS connection timeouts. IPVS stores timeouts with
+// a granularity of one second.
+type Timeouts struct {
+	TCP    time.Duration
+	TCPFin time.Duration
+	UDP    time.Duration
+}
+
+// String returns a string representation of Timeouts.
+func (t Timeouts) String() string {
+	return fmt.Sprintf("tcp %v, tcpfin %v, udp %v", t.TCP, t.TCPFin, t.UDP)
+}
+
 const (
 	ipvsSvcFlagPersist   = 0x1
 	ipvsSvcFlagHashed    = 0x2
@@ -390,6 +458,40 @@ func Flush() error {
 	return netlink.SendMessage(C.IPVS_CMD_FLUSH, family, 0)
 }
 
+// ZeroStats zeroes the statistics for all services and destinations in the
+// IPVS table.
+func ZeroStats() error {
+	return netlink.SendMessage(C.IPVS_CMD_ZERO, family, 0)
+}
+
+// ZeroServiceStats zeroes the statistics for the specified service and its
+// destinations.
+func ZeroServiceStats(svc Service) error {
+	ic := &ipvsCommand{Service: newIPVSService(&svc)}
+	return netlink.SendMessageMarshalled(C.IPVS_CMD_ZERO, family, 0, ic)
+}
+
+// GetTimeouts returns the connection timeouts that are currently configured
+// for IPVS.
+func GetTimeouts() (*Timeouts, error) {
+	var cfg ipvsConfig
+	if err := netlink.SendMessageUnmarshal(C.IPVS_CMD_GET_CONFIG, family, 0, &cfg); err != nil {
+		return nil, err
+	}
+	return cfg.toTimeouts(), nil
+}
+
+// SetTimeouts sets the connection timeouts for IPVS. A zero timeout leaves
+// the currently configured value unchanged. Non-zero timeouts must be between
+// one second and maxTimeout (about 24 days).
+func SetTimeouts(t Timeouts) error {
+	cfg, err := newIPVSConfig(&t)
+	if err != nil {
+		return err
+	}
+	return netlink.SendMessageMarshalled(C.IPVS_CMD_SET_CONFIG, family, 0, cfg)
+}
+
 // AddService adds the specified service to the IPVS table. Any destinations
 // associated with the given service will also be added.
 func AddService(svc Service) error {
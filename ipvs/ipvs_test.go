@@ -18,10 +18,12 @@ package ipvs
 
 import (
 	"bytes"
+	"math"
 	"net"
 	"reflect"
 	"syscall"
 	"testing"
+	"time"
 
 	"github.com/google/seesaw/netlink"
 )
@@ -393,6 +395,91 @@ func TestDestinationToIPVSDestination(t *testing.T) {
 	}
 }
 
+var timeoutsTests = []struct {
+	desc       string
+	timeouts   Timeouts
+	ipvsConfig ipvsConfig
+}{
+	{
+		"Zeroed structs",
+		Timeouts{},
+		ipvsConfig{},
+	},
+	{
+		"Default timeouts",
+		Timeouts{
+			TCP:    900 * time.Second,
+			TCPFin: 120 * time.Second,
+			UDP:    300 * time.Second,
+		},
+		ipvsConfig{
+			TimeoutTCP:    900,
+			TimeoutTCPFin: 120,
+			TimeoutUDP:    300,
+		},
+	},
+	{
+		"TCP timeout only",
+		Timeouts{
+			TCP: time.Hour,
+		},
+		ipvsConfig{
+			TimeoutTCP: 3600,
+		},
+	},
+	{
+		"Maximum UDP timeout",
+		Timeouts{
+			UDP: maxTimeout,
+		},
+		ipvsConfig{
+			TimeoutUDP: math.MaxInt32 / 1000,
+		},
+	},
+}
+
+func TestTimeoutsToIPVSConfig(t *testing.T) {
+	for _, test := range timeoutsTests {
+		got, err := newIPVSConfig(&test.timeouts)
+		if err != nil {
+			t.Errorf("newIPVSConfig() failed for %s: %v", test.desc, err)
+			continue
+		}
+		if !reflect.DeepEqual(*got, test.ipvsConfig) {
+			t.Errorf("newIPVSConfig() failed for %s - got %#v, want %#v",
+				test.desc, *got, test.ipvsConfig)
+		}
+	}
+}
+
+func TestTimeoutsToIPVSConfigInvalid(t *testing.T) {
+	tests := []struct {
+		desc     string
+		timeouts Timeouts
+	}{
+		{"Negative TCP timeout", Timeouts{TCP: -time.Second}},
+		{"Sub-second TCP FIN timeout", Timeouts{TCPFin: 500 * time.Millisecond}},
+		{"Overflowing UDP timeout", Timeouts{UDP: maxTimeout + time.Second}},
+		{"Negative int TCP timeout", Timeouts{TCP: (math.MaxInt32 + 1) * time.Second}},
+	}
+	for _, test := range tests {
+		if got, err := newIPVSConfig(&test.timeouts); err == nil {
+			t.Errorf("newIPVSConfig() succeeded for %s - got %#v, want error",
+				test.desc, *got)
+		}
+	}
+}
+
+func TestIPVSConfigToTimeouts(t *testing.T) {
+	for _, test := range timeoutsTests {
+		got := test.ipvsConfig.toTimeouts()
+		if !reflect.DeepEqual(*got, test.timeouts) {
+			t.Errorf("toTimeouts() failed for %s - got %#v, want %#v",
+				test.desc, *got, test.timeouts)
+		}
+	}
+}
+
 const (
 	nlTestCommand = 1
 	nlTestFamily  = 25
@@ -488,6 +575,28 @@ var (
 		0x00, 0x00, 0x00, 0x00,
 	}
 
+	nlmIPVSConfig = []byte{
+		0x2c, 0x00, 0x00, 0x00, 0x19, 0x00, 0x00, 0x00,
+		0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+		0x01, 0x01, 0x00, 0x00, 0x08, 0x00, 0x04, 0x00,
+		0x84, 0x03, 0x00, 0x00, 0x08, 0x00, 0x05, 0x00,
+		0x78, 0x00, 0x00, 0x00, 0x08, 0x00, 0x06, 0x00,
+		0x2c, 0x01, 0x00, 0x00,
+	}
+
+	nlmIPVSConfigUDPOnly = []byte{
+		0x1c, 0x00, 0x00, 0x00, 0x19, 0x00, 0x00, 0x00,
+		0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+		0x01, 0x01, 0x00, 0x00, 0x08, 0x00, 0x06, 0x00,
+		0x2c, 0x01, 0x00, 0x00,
+	}
+
+	testIPVSConfig = &ipvsConfig{
+		TimeoutTCP:    900,
+		TimeoutTCPFin: 120,
+		TimeoutUDP:    300,
+	}
+
 	testIPVSDestination = &ipvsDestination{
 		Address:        net.ParseIP("2002::cafe"),
 		Port:           53,
@@ -610,3 +719,51 @@ func TestServiceNetlinkUnmarshal(t *testing.T) {
 		t.Errorf("Got IPVS service %#v, want %#v", got.Service, &want)
 	}
 }
+
+func TestConfigNetlinkMarshal(t *testing.T) {
+	tests := []struct {
+		desc string
+		cfg  *ipvsConfig
+		want []byte
+	}{
+		{"All timeouts", testIPVSConfig, nlmIPVSConfig},
+		{"UDP timeout only", &ipvsConfig{TimeoutUDP: 300}, nlmIPVSConfigUDPOnly},
+	}
+	for _, test := range tests {
+		func() {
+			m, err := netlink.NewMessage(nlTestCommand, nlTestFamily, 0)
+			if err != nil {
+				t.Fatalf("Failed to make netlink message: %v", err)
+			}
+			defer m.Free()
+
+			if err := m.Marshal(test.cfg); err != nil {
+				t.Fatalf("%s: Failed to marshal: %v", test.desc, err)
+			}
+
+			got, err := m.Bytes()
+			if err != nil {
+				t.Fatalf("%s: Failed to get message bytes: %v", test.desc, err)
+			}
+			if !bytes.Equal(got, test.want) {
+				t.Errorf("%s: Got netlink bytes %#v, want %#v", test.desc, got, test.want)
+			}
+		}()
+	}
+}
+
+func TestConfigNetlinkUnmarshal(t *testing.T) {
+	m, err := netlink.NewMessageFromBytes(nlmIPVSConfig)
+	if err != nil {
+		t.Fatalf("Failed to make netlink message: %v", err)
+	}
+	defer m.Free()
+
+	got := &ipvsConfig{}
+	if err := m.Unmarshal(got); err != nil {
+		t.Fatalf("Failed to unmarshal message: %v", err)
+	}
+	if !reflect.DeepEqual(got, testIPVSConfig) {
+		t.Errorf("Got IPVS config %#v, want %#v", got, testIPVSConfig)
+	}
+}
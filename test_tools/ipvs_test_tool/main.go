@@ -19,7 +19,8 @@
 	the Linux kernel by flushing the IPVS table, adding specified
 	services and destinations, then getting the services and destinations
 	back from the kernel. If things worked correctly we should get the
-	same services and destinations that we added.
+	same services and destinations that we added. Connection timeouts are
+	tested in the same way.
 
 	Note: This must be run as root hence it being a manual testing tool.
 */
@@ -31,6 +32,7 @@ import (
 	"net"
 	"reflect"
 	"syscall"
+	"time"
 
 	"github.com/google/seesaw/ipvs"
 )
@@ -274,6 +276,17 @@ func testIPVSModification() {
 		compareSvc(&testSvc, []*ipvs.Service{svc})
 		compareDst(&testDst, svc.Destinations)
 
+		// Zero service statistics.
+		if err = ipvs.ZeroServiceStats(testSvc); err != nil {
+			log.Fatalf("ipvs.ZeroServiceStats() failed: %v\n", err)
+		}
+		if svc, err = ipvs.GetService(&testSvc); err != nil {
+			log.Fatalf("ipvs.GetService() failed: %v\n", err)
+		}
+		if svc.Statistics == nil || svc.Statistics.Stats != (ipvs.Stats{}) {
+			log.Printf("ERROR: Service statistics not zeroed - got %#v\n", svc.Statistics)
+		}
+
 		// Update service.
 		testSvc.Scheduler = "lc"
 		if err = ipvs.UpdateService(testSvc); err != nil {
@@ -326,6 +339,53 @@ func testIPVSModification() {
 	}
 }
 
+// testIPVSTimeouts sets the IPVS connection timeouts, then gets them back from
+// IPVS and compares them against what was set. The original timeouts are
+// restored afterwards.
+func testIPVSTimeouts() {
+	log.Println("Testing IPVS timeouts...")
+
+	orig, err := ipvs.GetTimeouts()
+	if err != nil {
+		log.Fatalf("ipvs.GetTimeouts() failed: %v\n", err)
+	}
+	log.Printf("=> Got timeouts %s\n", orig)
+
+	want := ipvs.Timeouts{
+		TCP:    901 * time.Second,
+		TCPFin: 121 * time.Second,
+		UDP:    301 * time.Second,
+	}
+	log.Printf("=> Setting timeouts %s\n", want)
+	if err := ipvs.SetTimeouts(want); err != nil {
+		log.Fatalf("ipvs.SetTimeouts() failed: %v\n", err)
+	}
+	got, err := ipvs.GetTimeouts()
+	if err != nil {
+		log.Fatalf("ipvs.GetTimeouts() failed: %v\n", err)
+	}
+	if *got != want {
+		log.Printf("ERROR: Timeouts do not match - got %s, want %s\n", got, want)
+	}
+
+	// A zero timeout should leave the configured value unchanged.
+	want.UDP = 302 * time.Second
+	log.Printf("=> Setting UDP timeout %v\n", want.UDP)
+	if err := ipvs.SetTimeouts(ipvs.Timeouts{UDP: want.UDP}); err != nil {
+		log.Fatalf("ipvs.SetTimeouts() failed: %v\n", err)
+	}
+	if got, err = ipvs.GetTimeouts(); err != nil {
+		log.Fatalf("ipvs.GetTimeouts() failed: %v\n", err)
+	}
+	if *got != want {
+		log.Printf("ERROR: Timeouts do not match - got %s, want %s\n", got, want)
+	}
+
+	if err := ipvs.SetTimeouts(*orig); err != nil {
+		log.Fatalf("Failed to restore IPVS timeouts: %v\n", err)
+	}
+}
+
 func main() {
 	if err := ipvs.Init(); err != nil {
 		log.Fatalf("IPVS initialisation failed: %v\n", err)
@@ -334,6 +394,7 @@ func main() {
 
 	testIPVS()
 	testIPVSModification()
+	testIPVSTimeouts()
 
 	// Clean up after ourselves...
 	if err := ipvs.Flush(); err != nil {